@@ -1,6 +1,7 @@
 use smartstring::alias::String as SmartString;
 
 use super::*;
+use crate::physical_plan::state::{partition_signature, CachedWindowGroups};
 
 pub(super) fn profile_name(
     s: &dyn PhysicalExpr,
@@ -24,15 +25,19 @@ fn execute_projection_cached_window_fns(
 ) -> PolarsResult<Vec<Series>> {
     // We partition by normal expression and window expression
     // - the normal expressions can run in parallel
-    // - the window expression take more memory and often use the same groupby keys and join tuples
-    //   so they are cached and run sequential
+    // - the window expressions take more memory and often use the same groupby keys and join
+    //   tuples, so within a single partition group they are cached and run sequentially; distinct
+    //   partition groups share no cache with each other, so those run in parallel too
 
     // the partitioning messes with column order, so we also store the idx
     // and use those to restore the original projection order
     #[allow(clippy::type_complexity)]
     // String: partition_name,
+    // Vec<Expr>: the partition_by of every window expr bucketed under that name (they're all
+    //            `partition_signature`-equal, so any one of them can be used to compute the
+    //            shared group tuples),
     // u32: index,
-    let mut windows: Vec<(String, Vec<(u32, Arc<dyn PhysicalExpr>)>)> = vec![];
+    let mut windows: Vec<(String, Vec<Expr>, Vec<(u32, Arc<dyn PhysicalExpr>)>)> = vec![];
     let mut other = Vec::with_capacity(exprs.len());
 
     // first we partition the window function by the values they group over.
@@ -45,11 +50,14 @@ fn execute_projection_cached_window_fns(
         let mut is_window = false;
         for e in e.into_iter() {
             if let Expr::Window { partition_by, .. } = e {
-                let groupby = format!("{:?}", partition_by.as_slice());
+                // this is also the key used to look up the cached group tuples in
+                // `ExecutionState`, so expressions that only differ in aggregation function
+                // bucket (and cache) together.
+                let groupby = partition_signature(partition_by.as_slice());
                 if let Some(tpl) = windows.iter_mut().find(|tpl| tpl.0 == groupby) {
-                    tpl.1.push((index, phys.clone()))
+                    tpl.2.push((index, phys.clone()))
                 } else {
-                    windows.push((groupby, vec![(index, phys.clone())]))
+                    windows.push((groupby, partition_by.clone(), vec![(index, phys.clone())]))
                 }
                 is_window = true;
                 break;
@@ -67,43 +75,90 @@ fn execute_projection_cached_window_fns(
             .collect::<PolarsResult<Vec<_>>>()
     })?;
 
-    for partition in windows {
-        // clear the cache for every partitioned group
-        let mut state = state.split();
-        // inform the expression it has window functions.
-        state.insert_has_window_function_flag();
+    // different partition groups (different `partition_by` key sets) share no groupby cache
+    // with each other, so we can dispatch them onto the thread pool and run them concurrently.
+    // within a single group we keep evaluating sequentially so the window-expr cache of that
+    // group can be reused across its expressions.
+    let windows_result = POOL.install(|| {
+        windows
+            .into_par_iter()
+            .map(|(groupby, partition_by, exprs)| {
+                // clear the cache for every partitioned group
+                let mut state = state.split();
+                // inform the expression it has window functions.
+                state.insert_has_window_function_flag();
 
-        // don't bother caching if we only have a single window function in this partition
-        if partition.1.len() == 1 {
-            state.remove_cache_window_flag();
-        } else {
-            state.insert_cache_window_flag();
-        }
+                // all expressions in this partition share the same `partition_by` (the
+                // `groupby` signature used to bucket them above), so the groupby keys and
+                // group tuples computed for the first window expression can be reused by
+                // every other window expression in the group, even when they differ in
+                // aggregation function or `order_by` (see issue #2523). A lone window
+                // expression has nothing to share the cache with, so skip the bookkeeping.
+                if exprs.len() == 1 {
+                    state.remove_cache_window_flag();
+                } else {
+                    state.insert_cache_window_flag();
+                    // compute the group tuples for this partition signature once, up front, and
+                    // seed the cache with them - every expression below evaluates under `state`
+                    // and looks its groups up under the same `groupby` key via
+                    // `ExecutionState::get_or_compute_window_groups`, so none of them computes
+                    // them again.
+                    state.get_or_compute_window_groups(&groupby, || {
+                        Ok(CachedWindowGroups {
+                            groups: Arc::new(compute_group_tuples(df, &partition_by)?),
+                        })
+                    })?;
+                }
 
-        for (index, e) in partition.1 {
-            if e.as_expression()
-                .unwrap()
-                .into_iter()
-                .filter(|e| matches!(e, Expr::Window { .. }))
-                .count()
-                == 1
-            {
-                state.insert_cache_window_flag();
-            }
-            // caching more than one window expression is a complicated topic for another day
-            // see issue #2523
-            else {
-                state.remove_cache_window_flag();
-            }
+                let mut out = Vec::with_capacity(exprs.len());
+                for (index, e) in exprs {
+                    let s = e.evaluate(df, &state)?;
+                    out.push((index, s));
+                }
+                PolarsResult::Ok(out)
+            })
+            .collect::<PolarsResult<Vec<_>>>()
+    })?;
+    selected_columns.extend(windows_result.into_iter().flatten());
 
-            let s = e.evaluate(df, &state)?;
-            selected_columns.push((index, s));
-        }
+    Ok(restore_projection_order(selected_columns))
+}
+
+/// Compute the row indices of every group in `df` formed by `partition_by`, i.e. the groupby
+/// keys/group tuples a window expression's `.over(partition_by)` groups over. This is the
+/// expensive part window expressions sharing a partition signature are meant to share, via
+/// [`ExecutionState::get_or_compute_window_groups`] - this function is only ever the `compute`
+/// closure passed to it, never called unconditionally.
+///
+/// Only simple column partition keys are supported here; a `partition_by` containing anything
+/// else (e.g. an expression) fails with a `ComputeError` rather than silently grouping on the
+/// wrong values.
+fn compute_group_tuples(df: &DataFrame, partition_by: &[Expr]) -> PolarsResult<Vec<Vec<u32>>> {
+    let key_columns = partition_by
+        .iter()
+        .map(|e| match e {
+            Expr::Column(name) => df.column(name.as_ref()),
+            _ => polars_bail!(
+                ComputeError: "window group tuples can only be computed for column partition keys, got {:?}", e
+            ),
+        })
+        .collect::<PolarsResult<Vec<_>>>()?;
+
+    let mut groups: PlHashMap<Vec<String>, Vec<u32>> = PlHashMap::default();
+    for row in 0..df.height() {
+        let key: Vec<String> = key_columns.iter().map(|s| format!("{}", s.get(row))).collect();
+        groups.entry(key).or_default().push(row as u32);
     }
+    Ok(groups.into_values().collect())
+}
 
-    selected_columns.sort_unstable_by_key(|tpl| tpl.0);
-    let selected_columns = selected_columns.into_iter().map(|tpl| tpl.1).collect();
-    Ok(selected_columns)
+/// Partition groups (and the `other` non-window expressions) are evaluated out of their
+/// original projection order - `other` runs on the thread pool, and each window partition group
+/// now does too - so this restores that order using the `u32` index stored alongside every
+/// result.
+fn restore_projection_order(mut indexed: Vec<(u32, Series)>) -> Vec<Series> {
+    indexed.sort_unstable_by_key(|tpl| tpl.0);
+    indexed.into_iter().map(|tpl| tpl.1).collect()
 }
 
 fn run_exprs_par(
@@ -119,19 +174,32 @@ fn run_exprs_par(
     })
 }
 
+/// Whether `expr` is a literal expression, i.e. a scalar that is allowed to broadcast to the
+/// dataframe height even under [`ExecutionState::strict_length_check`]. A length-1 `Series`
+/// produced by anything else (e.g. a reduction that should have matched the dataframe height)
+/// is not a literal and is rejected by strict mode instead.
+fn is_literal_expr(phys: &Arc<dyn PhysicalExpr>) -> bool {
+    matches!(phys.as_expression(), Some(Expr::Literal(_)))
+}
+
 pub(super) fn evaluate_physical_expressions(
     df: &mut DataFrame,
     cse_exprs: &[Arc<dyn PhysicalExpr>],
     exprs: &[Arc<dyn PhysicalExpr>],
     state: &ExecutionState,
     has_windows: bool,
-) -> PolarsResult<Vec<Series>> {
+) -> PolarsResult<(Vec<Series>, Vec<bool>)> {
     let runner = if has_windows {
         execute_projection_cached_window_fns
     } else {
         run_exprs_par
     };
 
+    // `exprs` is already in final projection order regardless of which runner evaluated it (the
+    // window runner restores that order itself), so the literal-ness of each output column can
+    // be read directly off `exprs` without threading it through either runner.
+    let is_literal: Vec<bool> = exprs.iter().map(is_literal_expr).collect();
+
     let selected_columns = if !cse_exprs.is_empty() {
         let tmp_cols = runner(df, cse_exprs, state)?;
         if has_windows {
@@ -165,12 +233,14 @@ pub(super) fn evaluate_physical_expressions(
         state.clear_window_expr_cache();
     }
 
-    Ok(selected_columns)
+    Ok((selected_columns, is_literal))
 }
 
 pub(super) fn check_expand_literals(
+    state: &ExecutionState,
     mut selected_columns: Vec<Series>,
     zero_length: bool,
+    is_literal: &[bool],
 ) -> PolarsResult<DataFrame> {
     let first_len = selected_columns[0].len();
     let mut df_height = 0;
@@ -187,12 +257,24 @@ pub(super) fn check_expand_literals(
             polars_ensure!(names.insert(name), duplicate = name);
         }
     }
-    // If all series are the same length it is ok. If not we can broadcast Series of length one.
+    // If all series are the same length it is ok. If not we can broadcast Series of length one,
+    // unless the caller opted into strict mode, in which case only an explicit scalar literal
+    // (`is_literal[i]`) is allowed to broadcast and any other length-1 series (e.g. one produced
+    // by a reduction that should have matched the dataframe height) raises a `ShapeMismatch`
+    // instead.
     if !all_equal_len {
+        let strict_length_check = state.strict_length_check();
         selected_columns = selected_columns
             .into_iter()
-            .map(|series| {
+            .enumerate()
+            .map(|(i, series)| {
                 Ok(if series.len() == 1 && df_height > 1 {
+                    polars_ensure!(
+                        !strict_length_check || is_literal[i],
+                        ShapeMismatch: "series of length {} doesn't match the dataframe height of {} \
+                        and broadcasting a non-literal result is disabled in strict mode",
+                        series.len(), df_height
+                    );
                     series.new_from_index(0, df_height)
                 } else if series.len() == df_height || series.len() == 0 {
                     series
@@ -221,4 +303,67 @@ pub(super) fn check_expand_literals(
         df
     };
     Ok(df)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn restore_projection_order_undoes_out_of_order_partition_groups() {
+        // simulates two independent window partition groups (e.g. `.sum().over("a")` and
+        // `.mean().over("b")`) finishing in whatever order the thread pool happens to schedule
+        // them in - the `u32` index recorded per expression must still restore the original
+        // projection order once the groups are merged back together.
+        let out_of_order = vec![
+            (2u32, Series::new("b_mean", &[20i32])),
+            (0u32, Series::new("a_sum", &[10i32])),
+            (1u32, Series::new("other", &[1i32])),
+        ];
+        let ordered = restore_projection_order(out_of_order);
+        let names: Vec<_> = ordered.iter().map(|s| s.name()).collect();
+        assert_eq!(names, ["a_sum", "other", "b_mean"]);
+    }
+
+    #[test]
+    fn check_expand_literals_lenient_mode_broadcasts_length_one_series() {
+        let state = ExecutionState::new();
+        let cols = vec![Series::new("a", &[1i32, 2, 3]), Series::new("b", &[9i32])];
+        let df = check_expand_literals(&state, cols, false, &[false, false]).unwrap();
+        assert_eq!(df.column("b").unwrap().len(), 3);
+    }
+
+    #[test]
+    fn check_expand_literals_strict_mode_still_broadcasts_a_true_literal() {
+        // `pl.lit(5)` in a `select` alongside longer columns must keep broadcasting even in
+        // strict mode - only a length-1 series that *isn't* an explicit literal is rejected.
+        let state = ExecutionState::new().with_strict_length_check(true);
+        let cols = vec![Series::new("a", &[1i32, 2, 3]), Series::new("b", &[9i32])];
+        let df = check_expand_literals(&state, cols, false, &[false, true]).unwrap();
+        assert_eq!(df.column("b").unwrap().len(), 3);
+    }
+
+    #[test]
+    fn check_expand_literals_strict_mode_rejects_broadcast_of_a_non_literal() {
+        // a length-1 `Series` that came from something other than a literal expression (e.g. a
+        // reduction that should have matched the dataframe height) must still be rejected.
+        let state = ExecutionState::new().with_strict_length_check(true);
+        let cols = vec![Series::new("a", &[1i32, 2, 3]), Series::new("b", &[9i32])];
+        let err = check_expand_literals(&state, cols, false, &[false, false]).unwrap_err();
+        assert!(matches!(err, PolarsError::ShapeMismatch(_)));
+    }
+
+    #[test]
+    fn compute_group_tuples_groups_rows_by_partition_key() {
+        let df = DataFrame::new_no_checks(vec![
+            Series::new("k", &["a", "b", "a", "b"]),
+            Series::new("v", &[1i32, 2, 3, 4]),
+        ]);
+        let mut groups = compute_group_tuples(&df, &[Expr::Column(Arc::from("k"))]).unwrap();
+        for g in groups.iter_mut() {
+            g.sort_unstable();
+        }
+        groups.sort_unstable();
+        assert_eq!(groups, vec![vec![0u32, 2], vec![1, 3]]);
+    }
 }
\ No newline at end of file
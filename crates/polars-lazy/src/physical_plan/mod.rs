@@ -0,0 +1,4 @@
+pub(crate) mod executors;
+pub(crate) mod state;
+
+pub use state::ExecutionState;
@@ -0,0 +1,195 @@
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::{Arc, Mutex};
+
+use polars_core::prelude::*;
+
+use super::*;
+
+const HAS_WINDOW_FUNCTION: u8 = 1 << 0;
+const CACHE_WINDOW_EXPR: u8 = 1 << 1;
+const STRICT_LENGTH_CHECK: u8 = 1 << 2;
+
+/// Groupby keys/group tuples (row indices per group) computed once for a window expression's
+/// `partition_by`, shared by every other window expression evaluated over the same partition
+/// group. See [`ExecutionState::get_or_compute_window_groups`].
+#[derive(Clone)]
+pub(crate) struct CachedWindowGroups {
+    pub(crate) groups: Arc<Vec<Vec<u32>>>,
+}
+
+type WindowCache = Mutex<PlHashMap<String, CachedWindowGroups>>;
+
+/// Normalize a window expression's `partition_by` into the signature used both to bucket window
+/// expressions into partition groups (see `execute_projection_cached_window_fns`) and to key the
+/// shared group-tuples cache below. Using this signature - rather than the full expression - is
+/// what lets `col("a").sum().over("k")` and `col("b").mean().over("k")` share one cache entry.
+pub(crate) fn partition_signature(partition_by: &[Expr]) -> String {
+    format!("{partition_by:?}")
+}
+
+/// State threaded through physical plan execution: feature flags for the expressions currently
+/// being evaluated (e.g. whether window expressions should cache their groupby keys) plus caches
+/// that are shared across the expressions of a single projection.
+#[derive(Clone)]
+pub struct ExecutionState {
+    flags: Arc<AtomicU8>,
+    window_cache: Arc<WindowCache>,
+}
+
+impl Default for ExecutionState {
+    fn default() -> Self {
+        Self {
+            flags: Arc::new(AtomicU8::new(0)),
+            window_cache: Arc::new(Mutex::new(PlHashMap::default())),
+        }
+    }
+}
+
+impl ExecutionState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fork this state, e.g. for a single partitioned group of window expressions: the flags are
+    /// copied so that toggling them for this group doesn't leak into a sibling group evaluated
+    /// concurrently, and the fork starts with an empty window cache of its own, so nothing one
+    /// group caches is visible to another; the expressions of a group all evaluate under the same
+    /// fork and share its cache.
+    pub(crate) fn split(&self) -> Self {
+        Self {
+            flags: Arc::new(AtomicU8::new(self.flags.load(Ordering::Relaxed))),
+            window_cache: Arc::new(Mutex::new(PlHashMap::default())),
+        }
+    }
+
+    fn set_flag(&self, flag: u8, on: bool) {
+        if on {
+            self.flags.fetch_or(flag, Ordering::Relaxed);
+        } else {
+            self.flags.fetch_and(!flag, Ordering::Relaxed);
+        }
+    }
+
+    fn has_flag(&self, flag: u8) -> bool {
+        self.flags.load(Ordering::Relaxed) & flag != 0
+    }
+
+    pub(crate) fn insert_has_window_function_flag(&self) {
+        self.set_flag(HAS_WINDOW_FUNCTION, true)
+    }
+
+    pub(crate) fn has_window_function_flag(&self) -> bool {
+        self.has_flag(HAS_WINDOW_FUNCTION)
+    }
+
+    pub(crate) fn insert_cache_window_flag(&self) {
+        self.set_flag(CACHE_WINDOW_EXPR, true)
+    }
+
+    pub(crate) fn remove_cache_window_flag(&self) {
+        self.set_flag(CACHE_WINDOW_EXPR, false)
+    }
+
+    pub(crate) fn cache_window_flag(&self) -> bool {
+        self.has_flag(CACHE_WINDOW_EXPR)
+    }
+
+    /// Drop every cached window groupby/group-tuples entry. Called once a whole projection has
+    /// finished evaluating, so the cache never outlives the `select`/`with_columns` call that
+    /// populated it.
+    pub(crate) fn clear_window_expr_cache(&self) {
+        self.window_cache.lock().unwrap().clear();
+    }
+
+    /// Look up the cached groupby keys/group tuples for the partition signature `key` (see
+    /// [`partition_signature`]), computing them via `compute` and storing them on a cache miss.
+    /// Whenever window caching is disabled for the current group (`cache_window_flag`), `compute`
+    /// is called every time and its result is not stored. Because
+    /// the key is the normalized `partition_by` rather than the full expression, every window
+    /// expression sharing that signature reuses the same entry regardless of which aggregation
+    /// function it applies.
+    pub(crate) fn get_or_compute_window_groups(
+        &self,
+        key: &str,
+        compute: impl FnOnce() -> PolarsResult<CachedWindowGroups>,
+    ) -> PolarsResult<CachedWindowGroups> {
+        if !self.cache_window_flag() {
+            return compute();
+        }
+        if let Some(cached) = self.window_cache.lock().unwrap().get(key) {
+            return Ok(cached.clone());
+        }
+        let computed = compute()?;
+        self.window_cache
+            .lock()
+            .unwrap()
+            .insert(key.to_string(), computed.clone());
+        Ok(computed)
+    }
+
+    /// Opt into strict length validation: with this enabled, a length-1 `Series` that does not
+    /// come from an explicit scalar literal raises a `ShapeMismatch` from `check_expand_literals`
+    /// instead of silently broadcasting to the dataframe height.
+    pub fn with_strict_length_check(self, strict: bool) -> Self {
+        self.set_flag(STRICT_LENGTH_CHECK, strict);
+        self
+    }
+
+    pub fn strict_length_check(&self) -> bool {
+        self.has_flag(STRICT_LENGTH_CHECK)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn window_groups_cache_is_keyed_by_partition_signature_not_compute_call() {
+        let state = ExecutionState::new();
+        state.insert_cache_window_flag();
+
+        let key = partition_signature(&[Expr::Column(Arc::from("k"))]);
+
+        // first window expression in the group (e.g. `col("a").sum().over("k")`) populates the
+        // cache.
+        let first = state
+            .get_or_compute_window_groups(&key, || {
+                Ok(CachedWindowGroups {
+                    groups: Arc::new(vec![vec![0, 2], vec![1, 3]]),
+                })
+            })
+            .unwrap();
+
+        // a second window expression sharing the same partition signature but a different
+        // aggregation function (e.g. `col("b").mean().over("k")`) must reuse the cached entry
+        // rather than recompute it - simulate that by having its `compute` closure panic if it
+        // is ever actually called.
+        let second = state
+            .get_or_compute_window_groups(&key, || panic!("group tuples recomputed"))
+            .unwrap();
+
+        assert_eq!(*first.groups, *second.groups);
+
+        // disabling the cache flag (a single-expression partition group) must always recompute.
+        state.remove_cache_window_flag();
+        let mut recomputed = false;
+        state
+            .get_or_compute_window_groups(&key, || {
+                recomputed = true;
+                Ok(CachedWindowGroups {
+                    groups: Arc::new(vec![]),
+                })
+            })
+            .unwrap();
+        assert!(recomputed);
+    }
+
+    #[test]
+    fn strict_length_check_flag_defaults_to_off_and_is_settable() {
+        let state = ExecutionState::new();
+        assert!(!state.strict_length_check());
+        let state = state.with_strict_length_check(true);
+        assert!(state.strict_length_check());
+    }
+}
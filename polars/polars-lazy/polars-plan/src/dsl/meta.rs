@@ -1,6 +1,6 @@
 use super::*;
 use crate::dsl::selector::Selector;
-use crate::logical_plan::projection::is_regex_projection;
+use crate::logical_plan::projection::{expand_expr, expand_expr_to_names, is_regex_projection};
 
 /// Specialized expressions for Categorical dtypes.
 pub struct MetaNameSpace(pub(crate) Expr);
@@ -36,21 +36,51 @@ impl MetaNameSpace {
     }
 
     /// Undo any renaming operation like `alias`, `keep_name`.
-    pub fn undo_aliases(mut self) -> Expr {
-        self.0.mutate().apply(|e| match e {
-            Expr::Alias(input, _)
-            | Expr::KeepName(input)
-            | Expr::RenameAlias { expr: input, .. } => {
-                // remove this node
-                *e = *input.clone();
-
-                // continue iteration
-                true
+    pub fn undo_aliases(self) -> Expr {
+        self.rewrite(|e| {
+            match e {
+                Expr::Alias(input, _)
+                | Expr::KeepName(input)
+                | Expr::RenameAlias { expr: input, .. } => {
+                    // remove this node
+                    *e = *input.clone();
+                },
+                _ => {},
             }
             // continue iteration
-            _ => true,
-        });
+            true
+        })
+    }
+
+    /// Visit every node of this expression tree, in pre-order, without mutating it.
+    /// Return `false` from `f` to stop the traversal early.
+    ///
+    /// This is pre-order only; there is no post-order variant. A generic post-order walk would
+    /// need to visit a node's children and then reconstruct the node from the (possibly
+    /// rewritten) results, which isn't possible through the public `Expr` API without depending
+    /// on the internal `Arena`/`AExpr` representation this traversal is meant to hide.
+    pub fn walk<F>(&self, mut f: F)
+    where
+        F: FnMut(&Expr) -> bool,
+    {
+        for e in self.0.into_iter() {
+            if !f(e) {
+                break;
+            }
+        }
+    }
 
+    /// Rewrite every node of this expression tree. `f` is called on each node and may mutate
+    /// it in place; return `true` to continue descending into (the possibly rewritten) node's
+    /// inputs, or `false` to stop the whole traversal early.
+    ///
+    /// Like `walk`, this only traverses pre-order (see its doc comment for why post-order isn't
+    /// offered here).
+    pub fn rewrite<F>(mut self, mut f: F) -> Expr
+    where
+        F: FnMut(&mut Expr) -> bool,
+    {
+        self.0.mutate().apply(|e| f(e));
         self.0
     }
 
@@ -99,4 +129,102 @@ impl MetaNameSpace {
         polars_ensure!(!matches!(self.0, Expr::Selector(_)), ComputeError: "nested selectors not allowed");
         Ok(Expr::Selector(Selector::new(self.0)))
     }
+
+    /// Expand this expression against `schema`, resolving any `Expr::Selector`,
+    /// `Expr::Wildcard`, `Expr::Columns`, `Expr::DtypeColumn` or regex `Expr::Column` - anywhere
+    /// in the expression tree, not only at the root - into the concrete column-producing
+    /// expressions it projects. Uses the expansion implemented in `logical_plan::projection`.
+    pub fn expand_selectors(&self, schema: &Schema) -> PolarsResult<Vec<Expr>> {
+        expand_expr(&self.0, schema)
+    }
+
+    /// Get the fully expanded list of output column names this expression will project
+    /// against `schema`, resolving selectors, wildcards, dtype-columns and regex projections.
+    pub fn column_names(&self, schema: &Schema) -> PolarsResult<Vec<Arc<str>>> {
+        expand_expr_to_names(&self.0, schema)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_schema() -> Schema {
+        Schema::from_iter([
+            Field::new("a", DataType::Int32),
+            Field::new("b", DataType::Int32),
+            Field::new("name", DataType::Utf8),
+        ])
+    }
+
+    #[test]
+    fn column_names_expands_wildcard() {
+        let schema = test_schema();
+        let names = MetaNameSpace(Expr::Wildcard).column_names(&schema).unwrap();
+        assert_eq!(
+            names,
+            vec![Arc::from("a"), Arc::from("b"), Arc::from("name")]
+        );
+    }
+
+    #[test]
+    fn column_names_expands_regex_column() {
+        let schema = test_schema();
+        let expr = Expr::Column(Arc::from("^a|b$"));
+        let mut names = MetaNameSpace(expr).column_names(&schema).unwrap();
+        names.sort();
+        assert_eq!(names, vec![Arc::from("a"), Arc::from("b")]);
+    }
+
+    #[test]
+    fn column_names_expands_selector_add_and_subtract() {
+        let schema = test_schema();
+        let mut selector = Selector::new(Expr::Wildcard);
+        selector.subtract.push(Expr::Column(Arc::from("name")));
+        let expr = Expr::Selector(selector);
+        let mut names = MetaNameSpace(expr).column_names(&schema).unwrap();
+        names.sort();
+        assert_eq!(names, vec![Arc::from("a"), Arc::from("b")]);
+    }
+
+    #[test]
+    fn column_names_expands_nested_wildcard_under_an_aggregation() {
+        let schema = test_schema();
+        let expr = Expr::Wildcard.sum();
+        let expanded = MetaNameSpace(expr).expand_selectors(&schema).unwrap();
+        assert_eq!(expanded.len(), 3);
+    }
+
+    #[test]
+    fn column_names_rejects_explicit_column_missing_from_schema() {
+        let schema = test_schema();
+        let expr = Expr::Columns(vec![Arc::from("nope")]);
+        assert!(MetaNameSpace(expr).column_names(&schema).is_err());
+    }
+
+    #[test]
+    fn walk_visits_every_node_in_pre_order_and_can_stop_early() {
+        let expr = Expr::Column(Arc::from("a")).sum().alias("a_sum");
+        let mut seen = Vec::new();
+        MetaNameSpace(expr.clone()).walk(|e| {
+            seen.push(e.clone());
+            true
+        });
+        assert_eq!(seen.len(), 3);
+        assert_eq!(seen[0], expr);
+
+        let mut visited = 0;
+        MetaNameSpace(expr).walk(|_| {
+            visited += 1;
+            false
+        });
+        assert_eq!(visited, 1);
+    }
+
+    #[test]
+    fn rewrite_replaces_matched_nodes_in_place() {
+        let expr = Expr::Column(Arc::from("a")).alias("b");
+        let rewritten = MetaNameSpace(expr).undo_aliases();
+        assert_eq!(rewritten, Expr::Column(Arc::from("a")));
+    }
 }
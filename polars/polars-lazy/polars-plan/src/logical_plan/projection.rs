@@ -0,0 +1,142 @@
+use std::sync::Arc;
+
+use regex::Regex;
+
+use crate::prelude::*;
+
+/// Returns `true` if `name` is a regex-style column selector, i.e. wrapped in `^...$`.
+pub(crate) fn is_regex_projection(name: &str) -> bool {
+    name.starts_with('^') && name.ends_with('$')
+}
+
+/// If `expr` is itself a "grouping" node - `Expr::Wildcard`, `Expr::Columns`,
+/// `Expr::DtypeColumn`, a regex `Expr::Column`, or `Expr::Selector` - resolve it against `schema`
+/// and return the concrete column names it selects. Returns `None` for every other expression,
+/// which projects exactly the one column `expr_output_name` would report.
+fn expand_grouping_node(expr: &Expr, schema: &Schema) -> PolarsResult<Option<Vec<Arc<str>>>> {
+    Ok(match expr {
+        Expr::Wildcard => Some(
+            schema
+                .iter_names()
+                .map(|name| Arc::from(name.as_str()))
+                .collect(),
+        ),
+        Expr::Columns(cols) => {
+            for name in cols.as_slice() {
+                polars_ensure!(schema.contains(name), ColumnNotFound: "{}", name);
+            }
+            Some(cols.iter().cloned().collect())
+        },
+        Expr::DtypeColumn(dtypes) => Some(
+            schema
+                .iter_names()
+                .zip(schema.iter_dtypes())
+                .filter(|(_, dtype)| dtypes.contains(dtype))
+                .map(|(name, _)| Arc::from(name.as_str()))
+                .collect(),
+        ),
+        Expr::Column(name) if is_regex_projection(name) => {
+            let re = Regex::new(name)
+                .map_err(|e| polars_err!(ComputeError: "invalid regex `{}`: {}", name, e))?;
+            Some(
+                schema
+                    .iter_names()
+                    .filter(|n| re.is_match(n))
+                    .map(|n| Arc::from(n.as_str()))
+                    .collect(),
+            )
+        },
+        Expr::Selector(selector) => {
+            let mut added = Vec::new();
+            for e in &selector.add {
+                added.extend(expand_single(e, schema)?);
+            }
+            let mut subtracted = Vec::new();
+            for e in &selector.subtract {
+                subtracted.extend(expand_single(e, schema)?);
+            }
+            added.retain(|name| !subtracted.contains(name));
+            Some(added)
+        },
+        _ => None,
+    })
+}
+
+fn expand_single(expr: &Expr, schema: &Schema) -> PolarsResult<Vec<Arc<str>>> {
+    match expand_grouping_node(expr, schema)? {
+        Some(names) => Ok(names),
+        None => Ok(vec![expr_output_name(expr)?]),
+    }
+}
+
+fn is_grouping_node(expr: &Expr) -> bool {
+    matches!(
+        expr,
+        Expr::Wildcard | Expr::Columns(_) | Expr::DtypeColumn(_) | Expr::Selector(_)
+    ) || matches!(expr, Expr::Column(name) if is_regex_projection(name))
+}
+
+/// Find every *maximal* grouping node anywhere in `expr`'s tree, not only at the root, so that
+/// e.g. a selector or wildcard nested under an aggregation (`cs.numeric().sum()`,
+/// `col("^a.*$").sum()`) is still recognized as expandable. Traversal stops descending as soon as
+/// it matches a grouping node, so a `Selector`'s own `add`/`subtract` expressions (already
+/// resolved by `expand_grouping_node` itself) are never separately counted here.
+fn find_grouping_nodes(expr: &Expr) -> Vec<Expr> {
+    let mut found = Vec::new();
+    let mut scratch = expr.clone();
+    scratch.mutate().apply(|e| {
+        if is_grouping_node(e) {
+            found.push(e.clone());
+            // swap the match for a leaf in the scratch copy, so its own inputs are not visited
+            // while the rest of the tree still is
+            *e = Expr::Column(Arc::from(""));
+        }
+        true
+    });
+    found
+}
+
+/// Fully expand `expr` against `schema`, resolving the single wildcard/selector/regex/dtype
+/// grouping node anywhere in its tree into one concrete, column-producing expression per matched
+/// column. An expression with no grouping node expands to itself, unchanged.
+///
+/// An expression combining more than one independently-expanding grouping node (e.g.
+/// `cs.numeric() + cs.string()`) has no single well-defined expansion - which column from the
+/// first selector pairs with which from the second is ambiguous - so this errors instead of
+/// silently expanding only the first one and leaving the rest unresolved.
+pub(crate) fn expand_expr(expr: &Expr, schema: &Schema) -> PolarsResult<Vec<Expr>> {
+    let nodes = find_grouping_nodes(expr);
+    let node = match nodes.as_slice() {
+        [] => return Ok(vec![expr.clone()]),
+        [node] => node.clone(),
+        _ => polars_bail!(
+            ComputeError:
+            "expression `{}` combines more than one selector/wildcard/regex/dtype-column \
+            expansion; combining independently-expanding selectors in a single expression is \
+            not supported",
+            expr
+        ),
+    };
+    let names = expand_single(&node, schema)?;
+    names
+        .into_iter()
+        .map(|name| {
+            let mut out = expr.clone();
+            let target = node.clone();
+            out.mutate().apply(|e| {
+                if *e == target {
+                    *e = Expr::Column(name.clone());
+                    false
+                } else {
+                    true
+                }
+            });
+            Ok(out)
+        })
+        .collect()
+}
+
+/// Fully expand `expr` against `schema` and return the output column names it will project.
+pub(crate) fn expand_expr_to_names(expr: &Expr, schema: &Schema) -> PolarsResult<Vec<Arc<str>>> {
+    expand_expr(expr, schema)?.iter().map(expr_output_name).collect()
+}